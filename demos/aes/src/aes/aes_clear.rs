@@ -0,0 +1,134 @@
+//! This module implements AES-128 on clear bytes. It is the reference for the homomorphic
+//! implementation, and is used by the client to encrypt its data in CTR mode.
+
+/// The AES substitution box
+pub const SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+/// The round constants of the key expansion
+pub const RCON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+/// Number of rounds of AES-128
+pub const NUM_ROUNDS: usize = 10;
+
+/// Multiplication by x (i.e. by 2) in GF(2^8)
+pub fn xtime(x: u8) -> u8 {
+    (x << 1) ^ if x & 0x80 != 0 { 0x1b } else { 0 }
+}
+
+/// Index in a block of the byte that ShiftRows moves to `index`. Bytes are in the order of the
+/// standard, column after column.
+pub fn shift_rows_source(index: usize) -> usize {
+    let (row, column) = (index % 4, index / 4);
+    row + 4 * ((column + row) % 4)
+}
+
+/// Increments a counter block, read as a big endian 128 bits integer
+pub fn increment_counter(counter: &mut [u8; 16]) {
+    for byte in counter.iter_mut().rev() {
+        *byte = byte.wrapping_add(1);
+        if *byte != 0 {
+            break;
+        }
+    }
+}
+
+/// Aes128: AES-128 block cipher on clear bytes, holding the expanded key
+pub struct Aes128 {
+    round_keys: [[u8; 16]; NUM_ROUNDS + 1],
+}
+
+impl Aes128 {
+    /// Constructor for `Aes128`: expands the key into the round keys
+    pub fn new(key: [u8; 16]) -> Self {
+        let mut round_keys = [[0u8; 16]; NUM_ROUNDS + 1];
+        round_keys[0] = key;
+
+        for round in 1..=NUM_ROUNDS {
+            let previous = round_keys[round - 1];
+
+            // SubWord(RotWord(w[i - 1])) ^ Rcon
+            let mut temp: [u8; 4] =
+                std::array::from_fn(|i| SBOX[previous[12 + (i + 1) % 4] as usize]);
+            temp[0] ^= RCON[round - 1];
+
+            for i in 0..16 {
+                let word_temp = if i < 4 {
+                    temp[i]
+                } else {
+                    round_keys[round][i - 4]
+                };
+                round_keys[round][i] = previous[i] ^ word_temp;
+            }
+        }
+
+        Self { round_keys }
+    }
+
+    pub fn round_keys(&self) -> &[[u8; 16]; NUM_ROUNDS + 1] {
+        &self.round_keys
+    }
+
+    /// Encrypts one block
+    pub fn encrypt_block(&self, block: [u8; 16]) -> [u8; 16] {
+        let mut state = block;
+        Self::add_round_key(&mut state, &self.round_keys[0]);
+
+        for round in 1..=NUM_ROUNDS {
+            for byte in state.iter_mut() {
+                *byte = SBOX[*byte as usize];
+            }
+            state = std::array::from_fn(|i| state[shift_rows_source(i)]);
+            if round != NUM_ROUNDS {
+                Self::mix_columns(&mut state);
+            }
+            Self::add_round_key(&mut state, &self.round_keys[round]);
+        }
+
+        state
+    }
+
+    /// Returns the key stream of CTR mode, starting from the counter block `iv`
+    pub fn ctr_key_stream(&self, iv: [u8; 16], num_blocks: usize) -> Vec<u8> {
+        let mut counter = iv;
+        let mut key_stream = Vec::with_capacity(16 * num_blocks);
+        for _ in 0..num_blocks {
+            key_stream.extend(self.encrypt_block(counter));
+            increment_counter(&mut counter);
+        }
+        key_stream
+    }
+
+    fn add_round_key(state: &mut [u8; 16], round_key: &[u8; 16]) {
+        for (byte, key_byte) in state.iter_mut().zip(round_key.iter()) {
+            *byte ^= key_byte;
+        }
+    }
+
+    fn mix_columns(state: &mut [u8; 16]) {
+        for column in state.chunks_mut(4) {
+            let a: [u8; 4] = column.try_into().unwrap();
+            for row in 0..4 {
+                let (a0, a1, a2, a3) =
+                    (a[row], a[(row + 1) % 4], a[(row + 2) % 4], a[(row + 3) % 4]);
+                column[row] = xtime(a0) ^ xtime(a1) ^ a1 ^ a2 ^ a3;
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! This module implements the key stream of AES-128 in CTR mode, on FPGA's.
+
+use std::collections::VecDeque;
+
+use tfhe::{FheUint8, ServerKey};
+
+use super::aes_clear::increment_counter;
+use super::aes_fpga::{AesFPGA, Byte};
+
+/// Number of counter blocks encrypted together each time the key stream runs out
+const BLOCKS_PER_BATCH: usize = 4;
+
+/// AesCtrStreamFPGA: the key stream of AES-128 in CTR mode, with an encrypted key. The counter
+/// block is read as a big endian 128 bits integer, as in NIST SP 800-38A.
+pub struct AesCtrStreamFPGA {
+    aes: AesFPGA,
+    counter: [u8; 16],
+    key_stream: VecDeque<Byte>,
+}
+
+impl AesCtrStreamFPGA {
+    /// Constructor for `AesCtrStreamFPGA`: arguments are the encrypted secret key, the initial
+    /// counter block, and the FHE server key.
+    pub fn new(key: [FheUint8; 16], iv: [u8; 16], server_key: &ServerKey) -> Self {
+        Self {
+            aes: AesFPGA::new(key, server_key),
+            counter: iv,
+            key_stream: VecDeque::with_capacity(16 * BLOCKS_PER_BATCH),
+        }
+    }
+
+    /// Outputs the next 8 bytes of the key stream (first value is oldest, last is newest)
+    pub fn next_64(&mut self) -> Vec<FheUint8> {
+        self.next_8_bytes()
+            .into_iter()
+            .map(|byte| FheUint8::try_from(byte).unwrap())
+            .collect()
+    }
+
+    /// Same as `next_64`, with the bytes as their shortint blocks
+    pub(crate) fn next_8_bytes(&mut self) -> Vec<Byte> {
+        if self.key_stream.len() < 8 {
+            self.encrypt_next_counters();
+        }
+        self.key_stream.drain(..8).collect()
+    }
+
+    /// Encrypts the next counter blocks, all together, and appends them to the key stream
+    fn encrypt_next_counters(&mut self) {
+        let counters: Vec<[u8; 16]> = (0..BLOCKS_PER_BATCH)
+            .map(|_| {
+                let counter = self.counter;
+                increment_counter(&mut self.counter);
+                counter
+            })
+            .collect();
+
+        self.key_stream
+            .extend(self.aes.encrypt_clear_blocks(&counters));
+    }
+}
@@ -0,0 +1,423 @@
+//! This module implements the AES-128 block cipher homomorphically, on FPGA's.
+//!
+//! Each byte is made of the 4 shortint blocks of a `FheUint8`, each holding 2 bits. All the
+//! bytes of a step are computed together, in `packs` of lookup tables. XORs of digits are
+//! bivariate lookup tables too, since adding digits carries between their bits.
+
+use tfhe::core_crypto::fpga::lookup_vector::LookupVector;
+use tfhe::integer::fpga::BelfortServerKey;
+use tfhe::integer::IntegerCiphertext;
+use tfhe::shortint::{Ciphertext, ServerKey as ShortintServerKey};
+use tfhe::{set_server_key, unset_server_key, FheUint8, ServerKey};
+
+use super::aes_clear::{shift_rows_source, xtime, NUM_ROUNDS, RCON, SBOX};
+
+/// Number of bits held by each shortint block
+const DIGIT_BITS: usize = 2;
+
+/// Number of shortint blocks in a byte
+pub(crate) const DIGITS_PER_BYTE: usize = 8 / DIGIT_BITS;
+
+/// A byte, as its shortint blocks, least significant first
+pub(crate) type Byte = Vec<Ciphertext>;
+
+/// Returns the shortint block `digit` of a clear byte
+pub(crate) fn clear_digit(byte: u8, digit: usize) -> u64 {
+    ((byte >> (DIGIT_BITS * digit)) & ((1 << DIGIT_BITS) - 1)) as u64
+}
+
+/// The lookup tables used by AES, generated once
+struct AesLookupVectors {
+    /// `sbox[output][high_nibble]` maps the low nibble of the input to the digit `output` of
+    /// `SBOX[16 * high_nibble + low_nibble]`, then of `xtime(SBOX[..])` for outputs past 4
+    sbox: Vec<Vec<LookupVector>>,
+    /// `select[k]` keeps its value if its selector is `k`, else returns 0
+    select: Vec<LookupVector>,
+    xor: LookupVector,
+    message_extract: LookupVector,
+}
+
+/// AesFPGA: AES-128 with an encrypted key. It owns the BelfortServerKey, and the round keys are
+/// expanded homomorphically when it is built.
+pub struct AesFPGA {
+    round_keys: Vec<Vec<Byte>>,
+    sk: ShortintServerKey,
+    fpga_key: BelfortServerKey,
+    luts: AesLookupVectors,
+}
+
+impl AesFPGA {
+    /// Constructor for `AesFPGA`: arguments are the encrypted secret key and the FHE server key.
+    /// The key bytes must be fresh encryptions. The server key must use 2 bits of message per
+    /// block.
+    pub fn new(key: [FheUint8; 16], server_key: &ServerKey) -> Self {
+        // Initialize Belfort server key
+        let mut fpga_key = BelfortServerKey::from(server_key);
+        fpga_key.connect();
+        set_server_key(fpga_key.clone());
+
+        let sk = fpga_key.pbs_key().clone().into_raw_parts();
+        assert_eq!(
+            sk.message_modulus.0,
+            1 << DIGIT_BITS,
+            "AesFPGA expects {DIGIT_BITS} bits of message per block"
+        );
+
+        let luts = AesLookupVectors::new(&sk, &fpga_key);
+
+        let mut ret = Self {
+            round_keys: Vec::with_capacity(NUM_ROUNDS + 1),
+            sk,
+            fpga_key,
+            luts,
+        };
+
+        let key = key.map(|byte| byte.into_raw_parts().0.blocks().to_vec());
+        ret.round_keys = ret.expand_key(key.to_vec());
+        ret
+    }
+
+    /// Encrypts blocks of encrypted bytes. All the blocks are computed together, so larger
+    /// batches of blocks result in larger packs.
+    pub fn encrypt_blocks(&self, blocks: &[[FheUint8; 16]]) -> Vec<[FheUint8; 16]> {
+        let state: Vec<Byte> = blocks
+            .iter()
+            .flatten()
+            .map(|byte| byte.clone().into_raw_parts().0.blocks().to_vec())
+            .collect();
+
+        let mut state = self.encrypt_states(state).into_iter();
+
+        blocks
+            .iter()
+            .map(|_| std::array::from_fn(|_| FheUint8::try_from(state.next().unwrap()).unwrap()))
+            .collect()
+    }
+
+    /// Encrypts blocks of clear bytes, which are trivially encrypted first
+    pub(crate) fn encrypt_clear_blocks(&self, blocks: &[[u8; 16]]) -> Vec<Byte> {
+        let state: Vec<Byte> = blocks
+            .iter()
+            .flatten()
+            .map(|&byte| {
+                (0..DIGITS_PER_BYTE)
+                    .map(|digit| self.sk.create_trivial(clear_digit(byte, digit)))
+                    .collect()
+            })
+            .collect();
+
+        self.encrypt_states(state)
+    }
+
+    /// Runs the rounds of AES on states of 16 bytes each, one after the other in `state`
+    fn encrypt_states(&self, state: Vec<Byte>) -> Vec<Byte> {
+        assert!(state.len() % 16 == 0);
+
+        // Initial AddRoundKey
+        let groups = state
+            .iter()
+            .enumerate()
+            .flat_map(|(i, byte)| {
+                let round_key_byte = &self.round_keys[0][i % 16];
+                (0..DIGITS_PER_BYTE)
+                    .map(move |digit| vec![byte[digit].clone(), round_key_byte[digit].clone()])
+            })
+            .collect();
+        let mut state = Self::into_bytes(self.xor_many(groups));
+
+        for round in 1..=NUM_ROUNDS {
+            let is_last = round == NUM_ROUNDS;
+
+            // SubBytes, which also outputs the products by 2 needed by MixColumns
+            let num_outputs = if is_last {
+                DIGITS_PER_BYTE
+            } else {
+                2 * DIGITS_PER_BYTE
+            };
+            let substituted = self.sub_bytes(&state, num_outputs);
+
+            // ShiftRows is a permutation of the bytes, then MixColumns and AddRoundKey are
+            // XORs only
+            let mut groups = Vec::with_capacity(state.len() * DIGITS_PER_BYTE);
+            for block_bytes in substituted.chunks(16) {
+                let shifted: Vec<&Byte> = (0..16)
+                    .map(|i| &block_bytes[shift_rows_source(i)])
+                    .collect();
+
+                for i in 0..16 {
+                    let (row, column) = (i % 4, i / 4);
+                    let a = |r: usize| shifted[4 * column + (row + r) % 4];
+                    let round_key_byte = &self.round_keys[round][i];
+
+                    for digit in 0..DIGITS_PER_BYTE {
+                        let xtime_digit = DIGITS_PER_BYTE + digit;
+                        let mut terms = if is_last {
+                            vec![shifted[i][digit].clone()]
+                        } else {
+                            // 2.a0 ^ 3.a1 ^ a2 ^ a3
+                            vec![
+                                a(0)[xtime_digit].clone(),
+                                a(1)[xtime_digit].clone(),
+                                a(1)[digit].clone(),
+                                a(2)[digit].clone(),
+                                a(3)[digit].clone(),
+                            ]
+                        };
+                        terms.push(round_key_byte[digit].clone());
+                        groups.push(terms);
+                    }
+                }
+            }
+
+            state = Self::into_bytes(self.xor_many(groups));
+        }
+
+        state
+    }
+
+    /// Expands the encrypted key into the 11 round keys. The 4 words of a round key only depend
+    /// on the previous round key, so each round key is computed at once.
+    fn expand_key(&self, key: Vec<Byte>) -> Vec<Vec<Byte>> {
+        let mut round_keys = Vec::with_capacity(NUM_ROUNDS + 1);
+        round_keys.push(key);
+
+        for round in 1..=NUM_ROUNDS {
+            let previous = &round_keys[round - 1];
+
+            // SubWord(RotWord(w[i - 1]))
+            let rotated: Vec<Byte> = (0..4).map(|i| previous[12 + (i + 1) % 4].clone()).collect();
+            let substituted = self.sub_bytes(&rotated, DIGITS_PER_BYTE);
+
+            // w[4 * round + j] is the XOR of w[4 * (round - 1) + i] for i <= j, the substituted
+            // word and the round constant
+            let mut groups = Vec::with_capacity(16 * DIGITS_PER_BYTE);
+            for i in 0..16 {
+                let (row, word) = (i % 4, i / 4);
+                let round_constant = if row == 0 { RCON[round - 1] } else { 0 };
+
+                for digit in 0..DIGITS_PER_BYTE {
+                    let mut terms: Vec<Ciphertext> = (0..=word)
+                        .map(|w| previous[4 * w + row][digit].clone())
+                        .collect();
+                    terms.push(substituted[row][digit].clone());
+
+                    let constant_digit = clear_digit(round_constant, digit);
+                    if constant_digit != 0 {
+                        terms.push(self.sk.create_trivial(constant_digit));
+                    }
+                    groups.push(terms);
+                }
+            }
+
+            round_keys.push(Self::into_bytes(self.xor_many(groups)));
+        }
+
+        round_keys
+    }
+
+    /// Applies the S-box to all the bytes, and returns `num_outputs` digits for each of them:
+    /// the digits of `SBOX[x]`, followed by the digits of `xtime(SBOX[x])` when `num_outputs`
+    /// is 8.
+    ///
+    /// The low nibble of each byte selects an entry in each row of 16 entries of the S-box. The
+    /// digits 2 and 3 of the byte then select the row, each with a bivariate lookup table.
+    fn sub_bytes(&self, bytes: &[Byte], num_outputs: usize) -> Vec<Byte> {
+        let n = bytes.len();
+        let m = num_outputs;
+
+        ////////////////////////////////////////////////////////////////////////
+        // Pack 1: entries of every row, indexed by the low nibble
+
+        let mut pack1: Vec<Ciphertext> = Vec::with_capacity(n * 16 * m);
+        let mut pack1_g: Vec<LookupVector> = Vec::with_capacity(n * 16 * m);
+
+        for byte in bytes {
+            let low_nibble = self.pack_digits(&byte[1], &byte[0]);
+            for high_nibble in 0..16 {
+                for output in 0..m {
+                    pack1.push(low_nibble.clone());
+                    pack1_g.push(self.luts.sbox[output][high_nibble]);
+                }
+            }
+        }
+
+        self.fpga_key
+            .apply_lookup_vector_packed_assign(&mut pack1, &pack1_g);
+
+        ////////////////////////////////////////////////////////////////////////
+        // Pack 2: select by digit 2, keeping only the rows matching it
+
+        let mut pack2: Vec<Ciphertext> = Vec::with_capacity(n * 16 * m);
+        let mut pack2_g: Vec<LookupVector> = Vec::with_capacity(n * 16 * m);
+
+        for (b, byte) in bytes.iter().enumerate() {
+            for high_nibble in 0..16 {
+                for output in 0..m {
+                    let entry = &pack1[(b * 16 + high_nibble) * m + output];
+                    pack2.push(self.pack_digits(&byte[2], entry));
+                    pack2_g.push(self.luts.select[high_nibble % 4]);
+                }
+            }
+        }
+
+        self.fpga_key
+            .apply_lookup_vector_packed_assign(&mut pack2, &pack2_g);
+
+        ////////////////////////////////////////////////////////////////////////
+        // Pack 3: the sum of the 4 rows sharing digit 3 is the only one kept, clean it
+
+        let mut pack3: Vec<Ciphertext> = Vec::with_capacity(n * 4 * m);
+
+        for b in 0..n {
+            for digit3 in 0..4 {
+                for output in 0..m {
+                    let rows = (0..4).map(|k| &pack2[(b * 16 + 4 * digit3 + k) * m + output]);
+                    pack3.push(self.sum(rows));
+                }
+            }
+        }
+
+        self.fpga_key
+            .apply_same_lookup_vector_packed_assign(&mut pack3, self.luts.message_extract);
+
+        ////////////////////////////////////////////////////////////////////////
+        // Pack 4: select by digit 3
+
+        let mut pack4: Vec<Ciphertext> = Vec::with_capacity(n * 4 * m);
+        let mut pack4_g: Vec<LookupVector> = Vec::with_capacity(n * 4 * m);
+
+        for (b, byte) in bytes.iter().enumerate() {
+            for digit3 in 0..4 {
+                for output in 0..m {
+                    let entry = &pack3[(b * 4 + digit3) * m + output];
+                    pack4.push(self.pack_digits(&byte[3], entry));
+                    pack4_g.push(self.luts.select[digit3]);
+                }
+            }
+        }
+
+        self.fpga_key
+            .apply_lookup_vector_packed_assign(&mut pack4, &pack4_g);
+
+        ////////////////////////////////////////////////////////////////////////
+        // Pack 5: sum the selected entries, and clean them
+
+        let mut pack5: Vec<Ciphertext> = Vec::with_capacity(n * m);
+
+        for b in 0..n {
+            for output in 0..m {
+                let rows = (0..4).map(|digit3| &pack4[(b * 4 + digit3) * m + output]);
+                pack5.push(self.sum(rows));
+            }
+        }
+
+        self.fpga_key
+            .apply_same_lookup_vector_packed_assign(&mut pack5, self.luts.message_extract);
+
+        pack5.chunks(m).map(<[Ciphertext]>::to_vec).collect()
+    }
+
+    /// XORs the terms of each group together, and returns one digit per group. The terms are
+    /// XORed two by two, with one pack for each level of the tree.
+    pub(crate) fn xor_many(&self, mut groups: Vec<Vec<Ciphertext>>) -> Vec<Ciphertext> {
+        while groups.iter().any(|terms| terms.len() > 1) {
+            let mut pack: Vec<Ciphertext> = groups
+                .iter()
+                .flat_map(|terms| terms.chunks_exact(2))
+                .map(|pair| self.pack_digits(&pair[0], &pair[1]))
+                .collect();
+
+            self.fpga_key
+                .apply_same_lookup_vector_packed_assign(&mut pack, self.luts.xor);
+
+            let mut xored = pack.into_iter();
+            groups = groups
+                .into_iter()
+                .map(|terms| {
+                    let mut next: Vec<Ciphertext> = xored.by_ref().take(terms.len() / 2).collect();
+                    if terms.len() % 2 == 1 {
+                        next.push(terms.last().unwrap().clone());
+                    }
+                    next
+                })
+                .collect();
+        }
+
+        groups
+            .into_iter()
+            .map(|mut terms| terms.pop().unwrap())
+            .collect()
+    }
+
+    /// Packs two digits into one ciphertext, as the input of a bivariate lookup table
+    fn pack_digits(&self, high: &Ciphertext, low: &Ciphertext) -> Ciphertext {
+        let mut packed = self
+            .sk
+            .unchecked_scalar_mul(high, high.message_modulus.0 as u8);
+        self.sk.unchecked_add_assign(&mut packed, low);
+        packed
+    }
+
+    /// Sums ciphertexts, without cleaning the result
+    fn sum<'a>(&self, mut cts: impl Iterator<Item = &'a Ciphertext>) -> Ciphertext {
+        let mut sum = cts.next().unwrap().clone();
+        for ct in cts {
+            self.sk.unchecked_add_assign(&mut sum, ct);
+        }
+        sum
+    }
+
+    fn into_bytes(digits: Vec<Ciphertext>) -> Vec<Byte> {
+        digits
+            .chunks(DIGITS_PER_BYTE)
+            .map(<[Ciphertext]>::to_vec)
+            .collect()
+    }
+}
+
+impl AesLookupVectors {
+    fn new(sk: &ShortintServerKey, fpga_key: &BelfortServerKey) -> Self {
+        let sbox = (0..2 * DIGITS_PER_BYTE)
+            .map(|output| {
+                (0..16)
+                    .map(|high_nibble| {
+                        sk.generate_lookup_vector_bivariate(&|digit1, digit0| {
+                            let value = SBOX[16 * high_nibble + (4 * digit1 + digit0) as usize];
+                            if output < DIGITS_PER_BYTE {
+                                clear_digit(value, output)
+                            } else {
+                                clear_digit(xtime(value), output - DIGITS_PER_BYTE)
+                            }
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let select = (0..4)
+            .map(|k| {
+                sk.generate_lookup_vector_bivariate(&|selector, value| {
+                    if selector == k {
+                        value
+                    } else {
+                        0
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            sbox,
+            select,
+            xor: sk.generate_lookup_vector_bivariate(&|x, y| x ^ y),
+            message_extract: fpga_key.lut_message_extract(),
+        }
+    }
+}
+
+impl Drop for AesFPGA {
+    fn drop(&mut self) {
+        self.fpga_key.disconnect();
+        unset_server_key();
+    }
+}
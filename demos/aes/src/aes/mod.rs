@@ -0,0 +1,11 @@
+mod aes_clear;
+pub use aes_clear::Aes128;
+
+mod aes_fpga;
+pub use aes_fpga::AesFPGA;
+
+mod aes_ctr_fpga;
+pub use aes_ctr_fpga::AesCtrStreamFPGA;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,111 @@
+use tfhe::prelude::*;
+use tfhe::{generate_keys, ConfigBuilder, FheUint64, FheUint8};
+
+use crate::{Aes128, AesCtrStreamFPGA, AesFPGA, TransCiphering};
+
+// Values for these tests come from FIPS-197, appendix C.1, and NIST SP 800-38A, appendix F.5.1
+
+const FIPS_197_KEY: &str = "000102030405060708090a0b0c0d0e0f";
+const FIPS_197_PLAINTEXT: &str = "00112233445566778899aabbccddeeff";
+const FIPS_197_CIPHERTEXT: &str = "69c4e0d86a7b0430d8cdb78070b4c55a";
+
+const SP_800_38A_KEY: &str = "2b7e151628aed2a6abf7158809cf4f3c";
+const SP_800_38A_IV: &str = "f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff";
+const SP_800_38A_PLAINTEXT: &str =
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51";
+const SP_800_38A_CIPHERTEXT: &str =
+    "874d6191b620e3261bef6864990db6ce9806f66b7970fdff8617187bb9fffdff";
+
+fn get_bytes_from_hexadecimal_string<const N: usize>(a: &str) -> [u8; N] {
+    assert_eq!(a.len(), 2 * N);
+    std::array::from_fn(|i| u8::from_str_radix(&a[2 * i..2 * i + 2], 16).unwrap())
+}
+
+#[test]
+fn aes_test_clear_block() {
+    let aes = Aes128::new(get_bytes_from_hexadecimal_string(FIPS_197_KEY));
+
+    let ciphertext = aes.encrypt_block(get_bytes_from_hexadecimal_string(FIPS_197_PLAINTEXT));
+
+    assert_eq!(
+        ciphertext,
+        get_bytes_from_hexadecimal_string::<16>(FIPS_197_CIPHERTEXT)
+    );
+}
+
+#[test]
+fn aes_test_clear_ctr() {
+    let aes = Aes128::new(get_bytes_from_hexadecimal_string(SP_800_38A_KEY));
+    let plaintext = get_bytes_from_hexadecimal_string::<32>(SP_800_38A_PLAINTEXT);
+
+    let key_stream = aes.ctr_key_stream(get_bytes_from_hexadecimal_string(SP_800_38A_IV), 2);
+    let ciphertext: Vec<u8> = plaintext
+        .iter()
+        .zip(key_stream.iter())
+        .map(|(p, k)| p ^ k)
+        .collect();
+
+    assert_eq!(
+        ciphertext,
+        get_bytes_from_hexadecimal_string::<32>(SP_800_38A_CIPHERTEXT)
+    );
+}
+
+#[test]
+fn aes_test_fhe_block_long() {
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+
+    let key = get_bytes_from_hexadecimal_string::<16>(FIPS_197_KEY);
+    let plaintext = get_bytes_from_hexadecimal_string::<16>(FIPS_197_PLAINTEXT);
+    let clear_aes = Aes128::new(key);
+
+    let cipher_key = key.map(|x| FheUint8::encrypt(x, &client_key));
+    let aes = AesFPGA::new(cipher_key, &server_key);
+
+    // Two blocks, to check that the blocks of a batch do not interfere
+    let blocks = [plaintext, clear_aes.encrypt_block(plaintext)];
+    let cipher_blocks = blocks.map(|block| block.map(|x| FheUint8::encrypt(x, &client_key)));
+
+    let cipher_outputs = aes.encrypt_blocks(&cipher_blocks);
+
+    for (block, cipher_output) in blocks.iter().zip(cipher_outputs.iter()) {
+        let output: Vec<u8> = cipher_output
+            .iter()
+            .map(|x| x.decrypt(&client_key))
+            .collect();
+        assert_eq!(output, clear_aes.encrypt_block(*block));
+    }
+}
+
+#[test]
+fn aes_test_fhe_transciphering_long() {
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+
+    let key = get_bytes_from_hexadecimal_string::<16>(SP_800_38A_KEY);
+    let iv = get_bytes_from_hexadecimal_string::<16>(SP_800_38A_IV);
+
+    // More words than a batch of counter blocks, to cover the refill of the key stream
+    let num_words: usize = 10;
+    let plaintext: Vec<u64> = (0..num_words as u64)
+        .map(|i| 0x0123_4567_89ab_cdef_u64.rotate_left(8 * i as u32) ^ i)
+        .collect();
+
+    // The client encrypts its data with AES-CTR
+    let clear_key_stream = Aes128::new(key).ctr_key_stream(iv, num_words.div_ceil(2));
+    let ciphertext: Vec<u64> = plaintext
+        .iter()
+        .zip(clear_key_stream.chunks(8))
+        .map(|(word, key_stream)| word ^ u64::from_be_bytes(key_stream.try_into().unwrap()))
+        .collect();
+
+    let cipher_key = key.map(|x| FheUint8::encrypt(x, &client_key));
+    let mut aes_ctr = AesCtrStreamFPGA::new(cipher_key, iv, &server_key);
+
+    for (word, cipher_word) in plaintext.iter().zip(ciphertext.iter()) {
+        let trans_ciphered = aes_ctr.trans_decrypt_64(FheUint64::encrypt_trivial(*cipher_word));
+        let decrypted: u64 = trans_ciphered.decrypt(&client_key);
+        assert_eq!(decrypted, *word);
+    }
+}
@@ -0,0 +1,74 @@
+use std::io;
+use std::io::Write;
+use std::time::Instant;
+use tfhe::prelude::*;
+use tfhe::{generate_keys, ConfigBuilder, FheUint64, FheUint8};
+
+use tfhe_aes::{Aes128, AesCtrStreamFPGA, TransCiphering};
+
+fn main() {
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+
+    let key = get_bytes_from_hexadecimal_string("2B7E151628AED2A6ABF7158809CF4F3C");
+    let iv = get_bytes_from_hexadecimal_string("F0F1F2F3F4F5F6F7F8F9FAFBFCFDFEFF");
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Client: encrypts its message with AES-CTR, and the AES key with FHE
+
+    let message = "Transciphering AES-CTR into FHE on FPGA's!";
+    let words: Vec<u64> = message
+        .as_bytes()
+        .chunks(8)
+        .map(|chunk| {
+            let mut word = [0u8; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u64::from_be_bytes(word)
+        })
+        .collect();
+
+    let key_stream = Aes128::new(key).ctr_key_stream(iv, words.len().div_ceil(2));
+    let cipher_words: Vec<u64> = words
+        .iter()
+        .zip(key_stream.chunks(8))
+        .map(|(word, key_stream)| word ^ u64::from_be_bytes(key_stream.try_into().unwrap()))
+        .collect();
+
+    let cipher_key = key.map(|x| FheUint8::encrypt(x, &client_key));
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Server: turns the AES-CTR ciphertext into FHE ciphertexts
+
+    println!("Key expansion starts");
+
+    let start = Instant::now();
+    let mut aes_ctr = AesCtrStreamFPGA::new(cipher_key, iv, &server_key);
+
+    println!("Key expansion took {:?}\n", start.elapsed());
+
+    let mut decrypted_bytes = Vec::with_capacity(8 * words.len());
+    for (i, cipher_word) in cipher_words.iter().enumerate() {
+        print!("\x1b[90m{:5}\x1b[0m {:016X} ", i, cipher_word);
+        io::stdout().flush().unwrap();
+
+        let start = Instant::now();
+        let fhe_word = aes_ctr.trans_decrypt_64(FheUint64::encrypt_trivial(*cipher_word));
+        let elapsed = start.elapsed();
+
+        // Client: decrypts the FHE ciphertext
+        let decrypted: u64 = fhe_word.decrypt(&client_key);
+        decrypted_bytes.extend(decrypted.to_be_bytes());
+
+        print!("{:016X}", decrypted);
+        print!(" \x1b[90m{:?}\x1b[0m\n", elapsed);
+    }
+
+    decrypted_bytes.truncate(message.len());
+    println!("\n{}", String::from_utf8_lossy(&decrypted_bytes));
+    println!("The end!");
+}
+
+fn get_bytes_from_hexadecimal_string(a: &str) -> [u8; 16] {
+    assert_eq!(a.len(), 32);
+    std::array::from_fn(|i| u8::from_str_radix(&a[2 * i..2 * i + 2], 16).unwrap())
+}
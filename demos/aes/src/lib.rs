@@ -0,0 +1,5 @@
+mod aes;
+pub use aes::{Aes128, AesCtrStreamFPGA, AesFPGA};
+
+mod trans_ciphering;
+pub use trans_ciphering::TransCiphering;
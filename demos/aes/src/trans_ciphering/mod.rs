@@ -0,0 +1,19 @@
+//! This module implements trans ciphering for the AES-CTR key stream, with the same interface as
+//! the Trivium streams.
+
+use crate::AesCtrStreamFPGA;
+
+use tfhe::FheUint64;
+pub use tfhe_trivium::TransCiphering;
+
+impl TransCiphering for AesCtrStreamFPGA {
+    /// `AesCtrStreamFPGA`: the next 8 bytes of the key stream form a word, the first byte being
+    /// the most significant one, which is XORed with the input cipher. Since each byte is made of
+    /// whole shortint blocks, the word is built without any computation.
+    fn trans_encrypt_64(&mut self, cipher: FheUint64) -> FheUint64 {
+        let blocks: Vec<_> = self.next_8_bytes().into_iter().rev().flatten().collect();
+        let key_stream = FheUint64::try_from(blocks).unwrap();
+
+        &cipher ^ &key_stream
+    }
+}
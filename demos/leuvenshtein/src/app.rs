@@ -21,16 +21,13 @@
 * SOFTWARE.
 */
 
-#[cfg(feature = "fpga")]
-use tfhe::core_crypto::fpga::keyswitch_bootstrap::KeyswitchBootstrapPacked;
-use tfhe::core_crypto::fpga::lookup_vector::LookupVector;
 use tfhe::shortint::prelude::*;
 
 use crate::data;
 use crate::enc_struct::EncStruct;
 use crate::util::{
-    self, apply_lookup_table_packed, unchecked_add_packed, unchecked_add_packed_assign,
-    unchecked_scalar_add_packed, unchecked_scalar_add_packed_assign, unchecked_scalar_mul_packed,
+    self, unchecked_add_packed, unchecked_add_packed_assign, unchecked_scalar_add_packed,
+    unchecked_scalar_add_packed_assign, unchecked_scalar_mul_packed,
     unchecked_scalar_mul_packed_assign, unchecked_sub_packed,
 };
 
@@ -197,32 +194,6 @@ impl App {
             db1_enc_matrix.push(name1_enc);
         }
 
-        let lut_min_vec_def = [0u64, 0, 0, 0, 1, 1, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0].to_vec();
-        let lut_eq_vec_def = [9u64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0].to_vec();
-        let lut_1eq_vec_def = [1u64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0].to_vec();
-
-        let lut_min = enc_struct
-            .sks
-            .generate_lookup_table_from_vector(&lut_min_vec_def);
-        let lut_1eq = enc_struct
-            .sks
-            .generate_lookup_table_from_vector(&lut_1eq_vec_def);
-        let lut_eq = enc_struct
-            .sks
-            .generate_lookup_table_from_vector(&lut_eq_vec_def);
-
-        let lut_1eq_vec = vec![lut_1eq; enc_struct.db_size];
-        let lut_eq_vec = vec![lut_eq; enc_struct.db_size];
-        let lut_min_vec = vec![lut_min; enc_struct.db_size];
-
-        let lut_1eq_fpga = LookupVector::new(&lut_1eq_vec_def);
-        let lut_eq_fpga = LookupVector::new(&lut_eq_vec_def);
-        let lut_min_fpga = LookupVector::new(&lut_min_vec_def);
-
-        let lut_1eq_vec_fpga = vec![lut_1eq_fpga; enc_struct.db_size];
-        let lut_eq_vec_fpga = vec![lut_eq_fpga; enc_struct.db_size];
-        let lut_min_vec_fpga = vec![lut_min_fpga; enc_struct.db_size];
-
         // Build and fill all the h_matrices
         let mut h_matrices: Vec<Vec<Vec<tfhe::shortint::Ciphertext>>> =
             Vec::with_capacity(enc_struct.db_size);
@@ -264,12 +235,6 @@ impl App {
         enc_struct.one_enc_vec = one_enc_vec;
         enc_struct.v_matrices = v_matrices;
         enc_struct.h_matrices = h_matrices;
-        enc_struct.lut_1eq_vec_sw = lut_1eq_vec;
-        enc_struct.lut_eq_vec_sw = lut_eq_vec;
-        enc_struct.lut_min_vec_sw = lut_min_vec;
-        enc_struct.lut_1eq_vec_fpga = lut_1eq_vec_fpga;
-        enc_struct.lut_eq_vec_fpga = lut_eq_vec_fpga;
-        enc_struct.lut_min_vec_fpga = lut_min_vec_fpga;
 
         enc_struct.time = Instant::now();
     }
@@ -325,21 +290,10 @@ impl App {
             v_matrices.push(v_matrix);
         }
 
-        let lut_min_vec_def = [0u64, 0, 0, 0, 1, 1, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0].to_vec();
-        let lut_min = enc_struct
-            .sks
-            .generate_lookup_table_from_vector(&lut_min_vec_def);
-        let lut_min_vec = vec![lut_min; enc_struct.db_size];
-
-        let fpga_lut_single = LookupVector::new(&lut_min_vec_def);
-        let lut_min_vec_fpga = vec![fpga_lut_single; enc_struct.db_size];
-
         enc_struct.max_factor = max_factor;
         enc_struct.th = th;
         enc_struct.v_matrices = v_matrices;
         enc_struct.h_matrices = h_matrices;
-        enc_struct.lut_min_vec_sw = lut_min_vec;
-        enc_struct.lut_min_vec_fpga = lut_min_vec_fpga;
 
         enc_struct.time = Instant::now();
     }
@@ -365,23 +319,10 @@ impl App {
 
                 unchecked_scalar_add_packed_assign(&enc_struct.sks, &mut eq1, 16);
 
-                let mut eq1_lut = Vec::new();
-
-                if fpga_enable {
-                    eq1_lut = eq1.clone();
-                    #[cfg(feature = "fpga")]
-                    enc_struct
-                        .fpga_key
-                        .fpga_utils
-                        .keyswitch_bootstrap_packed(&mut eq1_lut, &enc_struct.lut_1eq_vec_fpga);
-                } else {
-                    let ct = apply_lookup_table_packed(
-                        &enc_struct.sks,
-                        eq1.iter().collect(),
-                        &enc_struct.lut_1eq_vec_sw,
-                    );
-                    eq1_lut.extend(ct);
-                }
+                let mut eq1_lut = eq1.clone();
+                enc_struct
+                    .evaluator(fpga_enable)
+                    .apply_many(&mut eq1_lut, &vec![enc_struct.lut_1eq; eq1_lut.len()]);
 
                 let eq1_ref: Vec<&Ciphertext> = eq1_lut.iter().collect();
 
@@ -401,23 +342,10 @@ impl App {
 
                 unchecked_add_packed_assign(&enc_struct.sks, &mut eq2, eq1.iter().collect());
 
-                let mut eq2_lut = Vec::new();
-
-                if fpga_enable {
-                    eq2_lut = eq2.clone();
-                    #[cfg(feature = "fpga")]
-                    enc_struct.fpga_key.apply_lookup_vector_packed_assign(
-                        &mut eq2_lut,
-                        &enc_struct.lut_eq_vec_fpga,
-                    );
-                } else {
-                    let ct = apply_lookup_table_packed(
-                        &enc_struct.sks,
-                        eq2.iter().collect(),
-                        &enc_struct.lut_eq_vec_sw,
-                    );
-                    eq2_lut.extend(ct);
-                }
+                let mut eq2_lut = eq2.clone();
+                enc_struct
+                    .evaluator(fpga_enable)
+                    .apply_many(&mut eq2_lut, &vec![enc_struct.lut_eq; eq2_lut.len()]);
 
                 let vin = util::extract_number_elements(&enc_struct.v_matrices, index, j - 1);
                 let hin = util::extract_number_elements(&enc_struct.h_matrices, index - 1, j);
@@ -439,23 +367,10 @@ impl App {
                     v1.iter().collect(),
                 );
 
-                let mut ct_res = Vec::new();
-
-                if fpga_enable {
-                    ct_res = key.clone();
-                    #[cfg(feature = "fpga")]
-                    enc_struct
-                        .fpga_key
-                        .fpga_utils
-                        .keyswitch_bootstrap_packed(&mut ct_res, &enc_struct.lut_min_vec_fpga);
-                } else {
-                    let ct = apply_lookup_table_packed(
-                        &enc_struct.sks,
-                        key.iter().collect(),
-                        &enc_struct.lut_min_vec_sw,
-                    );
-                    ct_res.extend(ct);
-                }
+                let mut ct_res = key.clone();
+                enc_struct
+                    .evaluator(fpga_enable)
+                    .apply_many(&mut ct_res, &vec![enc_struct.lut_min; ct_res.len()]);
 
                 unchecked_scalar_add_packed_assign(&enc_struct.sks, &mut ct_res, 16);
 
@@ -513,21 +428,10 @@ impl App {
                     v1.iter().collect(),
                 );
 
-                let mut ct_res: Vec<Ciphertext>;
-                if fpga_enable {
-                    ct_res = key.clone();
-                    #[cfg(feature = "fpga")]
-                    enc_struct
-                        .fpga_key
-                        .fpga_utils
-                        .keyswitch_bootstrap_packed(&mut ct_res, &enc_struct.lut_min_vec_fpga);
-                } else {
-                    ct_res = apply_lookup_table_packed(
-                        &enc_struct.sks,
-                        key.iter().collect(),
-                        &enc_struct.lut_min_vec_sw,
-                    );
-                }
+                let mut ct_res = key.clone();
+                enc_struct
+                    .evaluator(fpga_enable)
+                    .apply_many(&mut ct_res, &vec![enc_struct.lut_min; ct_res.len()]);
 
                 unchecked_scalar_add_packed_assign(&enc_struct.sks, &mut ct_res, 16);
 
@@ -26,6 +26,7 @@
 use std::collections::HashMap;
 use std::time::Instant;
 use tfhe::core_crypto::fpga::lookup_vector::LookupVector;
+use tfhe::shortint::fpga::PackedLutEvaluator;
 use tfhe::shortint::prelude::*;
 
 #[cfg(feature = "fpga")]
@@ -53,12 +54,9 @@ pub struct EncStruct {
     pub one_enc_vec: Vec<tfhe::shortint::Ciphertext>,
     pub v_matrices: Vec<Vec<Vec<tfhe::shortint::Ciphertext>>>,
     pub h_matrices: Vec<Vec<Vec<tfhe::shortint::Ciphertext>>>,
-    pub lut_min_vec_sw: Vec<tfhe::shortint::server_key::LookupTable<Vec<u64>>>,
-    pub lut_1eq_vec_sw: Vec<tfhe::shortint::server_key::LookupTable<Vec<u64>>>,
-    pub lut_eq_vec_sw: Vec<tfhe::shortint::server_key::LookupTable<Vec<u64>>>,
-    pub lut_min_vec_fpga: Vec<LookupVector>,
-    pub lut_1eq_vec_fpga: Vec<LookupVector>,
-    pub lut_eq_vec_fpga: Vec<LookupVector>,
+    pub lut_min: LookupVector,
+    pub lut_1eq: LookupVector,
+    pub lut_eq: LookupVector,
 }
 
 #[cfg(feature = "fpga")]
@@ -80,10 +78,27 @@ pub struct EncStruct<'a> {
     pub one_enc_vec: Vec<tfhe::shortint::Ciphertext>,
     pub v_matrices: Vec<Vec<Vec<tfhe::shortint::Ciphertext>>>,
     pub h_matrices: Vec<Vec<Vec<tfhe::shortint::Ciphertext>>>,
-    pub lut_min_vec_sw: Vec<tfhe::shortint::server_key::LookupTable<Vec<u64>>>,
-    pub lut_1eq_vec_sw: Vec<tfhe::shortint::server_key::LookupTable<Vec<u64>>>,
-    pub lut_eq_vec_sw: Vec<tfhe::shortint::server_key::LookupTable<Vec<u64>>>,
-    pub lut_min_vec_fpga: Vec<LookupVector>,
-    pub lut_1eq_vec_fpga: Vec<LookupVector>,
-    pub lut_eq_vec_fpga: Vec<LookupVector>,
+    pub lut_min: LookupVector,
+    pub lut_1eq: LookupVector,
+    pub lut_eq: LookupVector,
+}
+
+#[cfg(not(feature = "fpga"))]
+impl EncStruct {
+    /// The backend evaluating the lookup tables
+    pub fn evaluator(&self, _fpga_enable: bool) -> &dyn PackedLutEvaluator {
+        &self.sks
+    }
+}
+
+#[cfg(feature = "fpga")]
+impl EncStruct<'_> {
+    /// The backend evaluating the lookup tables
+    pub fn evaluator(&self, fpga_enable: bool) -> &dyn PackedLutEvaluator {
+        if fpga_enable {
+            &*self.fpga_key
+        } else {
+            &self.sks
+        }
+    }
 }
@@ -29,6 +29,7 @@ use ratatui::widgets::{Block, Gauge, List, ListItem, Paragraph};
 use std::collections::HashMap;
 use std::time::Instant;
 use std::{error::Error, io};
+use tfhe::core_crypto::fpga::lookup_vector::LookupVector;
 use tfhe::shortint::prelude::*;
 
 use pad::PadStr;
@@ -178,12 +179,9 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<(
         one_enc_vec: Vec::new(),
         v_matrices: Vec::new(),
         h_matrices: Vec::new(),
-        lut_1eq_vec_sw: Vec::new(),
-        lut_eq_vec_sw: Vec::new(),
-        lut_min_vec_sw: Vec::new(),
-        lut_1eq_vec_fpga: Vec::new(),
-        lut_eq_vec_fpga: Vec::new(),
-        lut_min_vec_fpga: Vec::new(),
+        lut_min: LookupVector::new(&[0u64, 0, 0, 0, 1, 1, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0]),
+        lut_1eq: LookupVector::new(&[1u64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        lut_eq: LookupVector::new(&[9u64, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
     };
 
     #[cfg(feature = "fpga")]
@@ -29,7 +29,6 @@ use rayon::iter::*;
 
 use rayon::iter::IntoParallelRefMutIterator;
 use tfhe::shortint::prelude::*;
-use tfhe::shortint::server_key::LookupTableOwned;
 
 pub fn get_column(data: &Vec<Vec<Ciphertext>>, index: usize) -> Vec<Ciphertext> {
     let mut result = Vec::new();
@@ -85,23 +84,6 @@ pub fn write_number_elements(
     }
 }
 
-pub fn apply_lookup_table_packed(
-    sk: &tfhe::shortint::ServerKey,
-    cts: Vec<&Ciphertext>,
-    accs: &[LookupTableOwned],
-) -> Vec<Ciphertext> {
-    let mut ct_res: Vec<Ciphertext> = cts.iter().map(|&ct| ct.clone()).collect();
-
-    ct_res
-        .par_iter_mut()
-        .zip(accs.par_iter())
-        .for_each(|(ct, acc)| {
-            sk.apply_lookup_table_assign(ct, acc);
-        });
-
-    ct_res
-}
-
 pub fn unchecked_add_packed_assign(
     sk: &tfhe::shortint::ServerKey,
     cts_left: &mut Vec<Ciphertext>,
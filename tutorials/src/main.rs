@@ -2,7 +2,7 @@ use rand::Rng;
 use std::time::Instant;
 // Enable FPGA: Import the BelfortServerKey
 #[cfg(feature = "fpga")]
-use tfhe::integer::fpga::BelfortServerKey;
+use tfhe::integer::fpga::{BelfortConfigBuilder, BelfortServerKey};
 use tfhe::prelude::*;
 use tfhe::set_server_key;
 use tfhe::{ClientKey, ConfigBuilder, FheUint64};
@@ -30,7 +30,11 @@ fn main() {
     // Enable FPGA: Create FPGA key from your server and connect to it
     #[cfg(feature = "fpga")]
     let mut fpga_key = {
-        let mut fpga_key = BelfortServerKey::from(&server_key);
+        // Settings of the BELFORT_* environment variables, such as the backend
+        let fpga_config = BelfortConfigBuilder::from_env()
+            .and_then(|builder| builder.build())
+            .expect("Invalid BELFORT_* environment variables");
+        let mut fpga_key = BelfortServerKey::from(&server_key).with_config(fpga_config);
         fpga_key.connect();
         set_server_key(fpga_key.clone());
         fpga_key
@@ -58,8 +62,13 @@ fn main() {
         + encrypted_value2 * encrypted_weight2
         + encrypted_value3 * encrypted_weight3;
 
+    // The backend is the one set in BELFORT_BACKEND: hardware, emulated or simulated
     #[cfg(feature = "fpga")]
-    println!("Execution time on FPGA: {:?}", time_start.elapsed());
+    println!(
+        "Execution time on FPGA ({}): {:?}",
+        fpga_key.backend(),
+        time_start.elapsed()
+    );
     #[cfg(not(feature = "fpga"))]
     println!("Execution time on CPU: {:?}", time_start.elapsed());
 